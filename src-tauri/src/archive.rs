@@ -0,0 +1,117 @@
+// Embedded download archive backed by `sled`.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::OnceLock;
+
+static DB: OnceLock<Result<sled::Db, String>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub episode_id: String,
+    pub series_name: String,
+    pub title: String,
+    pub source_url: String,
+    pub file_path: String,
+    pub file_size: u64,
+    pub downloaded_at: u64,
+    pub subtitle_lang: Option<String>,
+}
+
+/// Handle onto the archive database, opened once per process.
+pub struct FileCache {
+    db: &'static sled::Db,
+}
+
+impl FileCache {
+    /// Open (or reuse the already-open) archive database under `path`.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let db = DB
+            .get_or_init(|| sled::open(path).map_err(|e| format!("failed to open download archive: {e}")))
+            .as_ref()
+            .map_err(Clone::clone)?;
+
+        Ok(Self { db })
+    }
+
+    pub fn insert(&self, entry: &ArchiveEntry) -> Result<(), String> {
+        let value = serde_json::to_vec(entry).map_err(|e| e.to_string())?;
+        self.db
+            .insert(entry.episode_id.as_bytes(), value)
+            .map_err(|e| e.to_string())?;
+        self.db.flush().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn get(&self, episode_id: &str) -> Result<Option<ArchiveEntry>, String> {
+        match self.db.get(episode_id.as_bytes()).map_err(|e| e.to_string())? {
+            Some(bytes) => {
+                let entry = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+                Ok(Some(entry))
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn is_downloaded(&self, episode_id: &str) -> Result<bool, String> {
+        Ok(self.get(episode_id)?.is_some())
+    }
+
+    pub fn remove(&self, episode_id: &str) -> Result<(), String> {
+        self.db
+            .remove(episode_id.as_bytes())
+            .map_err(|e| e.to_string())?;
+        self.db.flush().map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    pub fn list_for_series(&self, series_name: &str) -> Result<Vec<ArchiveEntry>, String> {
+        let mut entries = Vec::new();
+        for item in self.db.iter() {
+            let (_, value) = item.map_err(|e| e.to_string())?;
+            let entry: ArchiveEntry = serde_json::from_slice(&value).map_err(|e| e.to_string())?;
+            if entry.series_name == series_name {
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a, b| b.downloaded_at.cmp(&a.downloaded_at));
+        Ok(entries)
+    }
+}
+
+fn archive_path(app_handle: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    let app_dir = app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or("Could not get app data directory")?;
+
+    Ok(app_dir.join("archive.sled"))
+}
+
+pub fn open_cache(app_handle: &tauri::AppHandle) -> Result<FileCache, String> {
+    FileCache::open(&archive_path(app_handle)?)
+}
+
+// List archived downloads for a series
+#[tauri::command]
+pub async fn list_downloaded(
+    app_handle: tauri::AppHandle,
+    series_name: String,
+) -> Result<Vec<ArchiveEntry>, String> {
+    open_cache(&app_handle)?.list_for_series(&series_name)
+}
+
+// Check whether an episode has already been downloaded
+#[tauri::command]
+pub async fn is_downloaded(app_handle: tauri::AppHandle, episode_id: String) -> Result<bool, String> {
+    open_cache(&app_handle)?.is_downloaded(&episode_id)
+}
+
+// Remove an episode from the archive so it can be re-downloaded
+#[tauri::command]
+pub async fn remove_from_archive(
+    app_handle: tauri::AppHandle,
+    episode_id: String,
+) -> Result<(), String> {
+    open_cache(&app_handle)?.remove(&episode_id)
+}
@@ -0,0 +1,312 @@
+// Manages concurrent, cancellable per-series download jobs.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use serde::Serialize;
+use tauri::Manager;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::{watch, Mutex, Semaphore};
+
+use crate::{find_downloaded_file, get_python_script_path, subtitle_lang_from_options, Config};
+
+struct JobHandle {
+    cancel_tx: watch::Sender<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DownloadStatus {
+    pub downloading: bool,
+    pub active_jobs: usize,
+}
+
+/// Tauri-managed state tracking in-flight per-series download jobs.
+pub struct DownloadManager {
+    jobs: Arc<Mutex<HashMap<String, JobHandle>>>,
+    downloading: AtomicBool,
+    active_count: AtomicUsize,
+    semaphore: Arc<Semaphore>,
+}
+
+impl DownloadManager {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            downloading: AtomicBool::new(false),
+            active_count: AtomicUsize::new(0),
+            semaphore: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    pub fn status(&self) -> DownloadStatus {
+        DownloadStatus {
+            downloading: self.downloading.load(Ordering::SeqCst),
+            active_jobs: self.active_count.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Start downloading a single series as its own cancellable job.
+    pub async fn start(
+        self: Arc<Self>,
+        app_handle: tauri::AppHandle,
+        window: tauri::Window,
+        extractor: crate::tver::Extractor,
+        config: Config,
+        series_name: String,
+    ) -> Result<(), String> {
+        if self.jobs.lock().await.contains_key(&series_name) {
+            return Err(format!("{series_name} is already downloading"));
+        }
+
+        let series = config
+            .series
+            .iter()
+            .find(|s| s.name == series_name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown series: {series_name}"))?;
+
+        let config_path = app_handle
+            .path_resolver()
+            .app_data_dir()
+            .ok_or("Could not get app data directory")?
+            .join("config.json");
+
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        self.jobs
+            .lock()
+            .await
+            .insert(series_name.clone(), JobHandle { cancel_tx });
+
+        self.active_count.fetch_add(1, Ordering::SeqCst);
+        self.downloading.store(true, Ordering::SeqCst);
+
+        let manager = self.clone();
+        let permit = self.semaphore.clone();
+        let download_path = config.download_path.clone();
+        let subtitle_lang = subtitle_lang_from_options(&config.yt_dlp_options);
+
+        tauri::async_runtime::spawn(async move {
+            let _permit = permit.acquire_owned().await;
+
+            let result = run_job(&app_handle, &window, &config_path, &series, cancel_rx).await;
+
+            // Reconcile regardless of exit status: a partial failure (one
+            // bad episode) still leaves other episodes from this run fully
+            // downloaded, and find_downloaded_file already tolerates
+            // episodes that never finished.
+            reconcile_archive(&app_handle, &extractor, &series, &download_path, subtitle_lang)
+                .await;
+
+            manager.finish(&series.name, result).await;
+        });
+
+        Ok(())
+    }
+
+    pub async fn cancel(&self, series_name: &str) -> Result<(), String> {
+        let jobs = self.jobs.lock().await;
+        let job = jobs
+            .get(series_name)
+            .ok_or_else(|| format!("{series_name} is not downloading"))?;
+        job.cancel_tx.send(true).map_err(|e| e.to_string())
+    }
+
+    pub async fn cancel_all(&self) {
+        let jobs = self.jobs.lock().await;
+        for job in jobs.values() {
+            let _ = job.cancel_tx.send(true);
+        }
+    }
+
+    async fn finish(&self, series_name: &str, result: Result<(), String>) {
+        self.jobs.lock().await.remove(series_name);
+        self.active_count.fetch_sub(1, Ordering::SeqCst);
+        if self.active_count.load(Ordering::SeqCst) == 0 {
+            self.downloading.store(false, Ordering::SeqCst);
+        }
+        if let Err(err) = result {
+            eprintln!("download job for {series_name} ended: {err}");
+        }
+    }
+}
+
+async fn run_job(
+    app_handle: &tauri::AppHandle,
+    window: &tauri::Window,
+    config_path: &std::path::Path,
+    series: &crate::Series,
+    mut cancel_rx: watch::Receiver<bool>,
+) -> Result<(), String> {
+    let script_path = get_python_script_path(app_handle)?;
+
+    let mut child = Command::new("python3")
+        .arg(&script_path)
+        .arg("--config")
+        .arg(config_path)
+        .arg("--series")
+        .arg(&series.name)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start download: {e}"))?;
+
+    let stdout = child.stdout.take();
+    let window = window.clone();
+    let series_name = series.name.clone();
+    let stdout_buf: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
+    let lines_task = stdout.map(|stdout| {
+        let buf = stdout_buf.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut parser = crate::progress::ProgressParser::new(series_name);
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(progress) = parser.parse_line(&line) {
+                    let _ = window.emit("download-progress", progress);
+                }
+                let mut buf = buf.lock().await;
+                buf.push_str(&line);
+                buf.push('\n');
+            }
+        })
+    });
+
+    let stderr = child.stderr.take();
+    let stderr_buf: Arc<Mutex<String>> = Arc::new(Mutex::new(String::new()));
+    let stderr_task = stderr.map(|stderr| {
+        let buf = stderr_buf.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let mut buf = buf.lock().await;
+                buf.push_str(&line);
+                buf.push('\n');
+            }
+        })
+    });
+
+    let result = loop {
+        tokio::select! {
+            status = child.wait() => {
+                let status = status.map_err(|e| e.to_string())?;
+                break if status.success() {
+                    Ok(())
+                } else {
+                    Err("Download failed".to_string())
+                };
+            }
+            _ = cancel_rx.changed() => {
+                if *cancel_rx.borrow() {
+                    let _ = child.kill().await;
+                    break Err("Download cancelled".to_string());
+                }
+            }
+        }
+    };
+
+    if let Some(task) = lines_task {
+        let _ = task.await;
+    }
+    if let Some(task) = stderr_task {
+        let _ = task.await;
+    }
+
+    if let Err(message) = &result {
+        let stdout_text = stdout_buf.lock().await.clone();
+        let stderr_text = stderr_buf.lock().await.clone();
+        let report = crate::reports::FailureReport::new(&series.name, &series.url, "download_episodes")
+            .with_stdout(stdout_text)
+            .with_stderr(if stderr_text.is_empty() {
+                message.clone()
+            } else {
+                stderr_text
+            });
+        let _ = crate::reports::file_report(app_handle, &report);
+    }
+
+    result
+}
+
+async fn reconcile_archive(
+    app_handle: &tauri::AppHandle,
+    extractor: &crate::tver::Extractor,
+    series: &crate::Series,
+    download_path: &str,
+    subtitle_lang: Option<String>,
+) {
+    let Ok(cache) = crate::archive::open_cache(app_handle) else {
+        return;
+    };
+    let Ok(episodes) = extractor.fetch_episodes(&series.url).await else {
+        return;
+    };
+
+    for episode in episodes {
+        if cache.is_downloaded(&episode.id).unwrap_or(false) {
+            continue;
+        }
+
+        let series_dir = std::path::Path::new(download_path).join(&series.name);
+        let Some(file_path) = find_downloaded_file(&series_dir, &episode.id) else {
+            continue;
+        };
+        let file_size = std::fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+
+        let entry = crate::archive::ArchiveEntry {
+            episode_id: episode.id,
+            series_name: series.name.clone(),
+            title: episode.title,
+            source_url: episode.url,
+            file_path: file_path.to_string_lossy().to_string(),
+            file_size,
+            downloaded_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            subtitle_lang: subtitle_lang.clone(),
+        };
+        let _ = cache.insert(&entry);
+    }
+}
+
+// Start downloading a single series
+#[tauri::command]
+pub async fn start_download(
+    app_handle: tauri::AppHandle,
+    window: tauri::Window,
+    manager: tauri::State<'_, Arc<DownloadManager>>,
+    extractor: tauri::State<'_, crate::tver::Extractor>,
+    config: Config,
+    series_name: String,
+) -> Result<(), String> {
+    manager
+        .inner()
+        .clone()
+        .start(app_handle, window, extractor.inner().clone(), config, series_name)
+        .await
+}
+
+// Cancel a single series' in-progress download
+#[tauri::command]
+pub async fn cancel_download(
+    manager: tauri::State<'_, Arc<DownloadManager>>,
+    series_name: String,
+) -> Result<(), String> {
+    manager.cancel(&series_name).await
+}
+
+// Cancel every in-progress download
+#[tauri::command]
+pub async fn cancel_all(manager: tauri::State<'_, Arc<DownloadManager>>) -> Result<(), String> {
+    manager.cancel_all().await;
+    Ok(())
+}
+
+// Report overall download status for polling UIs
+#[tauri::command]
+pub async fn download_status(
+    manager: tauri::State<'_, Arc<DownloadManager>>,
+) -> Result<DownloadStatus, String> {
+    Ok(manager.status())
+}
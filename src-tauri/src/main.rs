@@ -2,9 +2,17 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 use serde::{Deserialize, Serialize};
-use std::process::{Command, Stdio};
+use std::sync::Arc;
 use tauri::Manager;
 
+mod archive;
+mod download_manager;
+mod progress;
+mod protocol;
+mod region_guard;
+mod reports;
+mod tver;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Config {
     series: Vec<Series>,
@@ -12,6 +20,21 @@ struct Config {
     archive_file: String,
     debug: bool,
     yt_dlp_options: Vec<String>,
+    #[serde(default = "default_required_country")]
+    required_country: String,
+    #[serde(default = "default_geo_ip_endpoints")]
+    geo_ip_endpoints: Vec<String>,
+}
+
+fn default_required_country() -> String {
+    "JP".to_string()
+}
+
+fn default_geo_ip_endpoints() -> Vec<String> {
+    vec![
+        "https://ipapi.co/json/".to_string(),
+        "https://ip.seeip.org/geoip".to_string(),
+    ]
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -39,41 +62,6 @@ struct DownloadProgress {
     progress: f32,
 }
 
-// Check VPN connection
-#[tauri::command]
-async fn check_vpn() -> Result<String, String> {
-    let client = reqwest::Client::new();
-    
-    // Try multiple services
-    let services = vec![
-        "https://ipapi.co/json/",
-        "https://ip.seeip.org/geoip",
-    ];
-    
-    for service in services {
-        if let Ok(response) = client.get(service).send().await {
-            if let Ok(data) = response.json::<serde_json::Value>().await {
-                let country = data.get("country_code")
-                    .or_else(|| data.get("cc"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("");
-                
-                let ip = data.get("ip")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("unknown");
-                
-                if country == "JP" {
-                    return Ok(format!("Connected via Japan IP ({})", ip));
-                } else {
-                    return Err(format!("Not connected to Japan VPN (detected: {}, IP: {})", country, ip));
-                }
-            }
-        }
-    }
-    
-    Err("Could not verify VPN connection".to_string())
-}
-
 // Load configuration
 #[tauri::command]
 async fn load_config(app_handle: tauri::AppHandle) -> Result<Config, String> {
@@ -92,11 +80,15 @@ async fn load_config(app_handle: tauri::AppHandle) -> Result<Config, String> {
             debug: false,
             yt_dlp_options: vec![
                 "-o".to_string(),
-                "%(series)s/%(title)s.%(ext)s".to_string(),
+                // The `[%(id)s]` suffix lets us match a finished download back
+                // to its archive entry by id instead of by sanitized title.
+                "%(series)s/%(title)s [%(id)s].%(ext)s".to_string(),
                 "--write-sub".to_string(),
                 "--sub-lang".to_string(),
                 "ja".to_string(),
             ],
+            required_country: default_required_country(),
+            geo_ip_endpoints: default_geo_ip_endpoints(),
         };
         
         std::fs::create_dir_all(&app_dir).map_err(|e| e.to_string())?;
@@ -137,81 +129,115 @@ fn get_python_script_path(app_handle: &tauri::AppHandle) -> Result<std::path::Pa
 
 // Fetch episodes for a series
 #[tauri::command]
-async fn fetch_episodes(app_handle: tauri::AppHandle, series_url: String) -> Result<Vec<Episode>, String> {
-    let script_path = get_python_script_path(&app_handle)?;
-    
-    // Call Python script to get episodes
-    let output = Command::new("python3")
-        .arg(&script_path)
-        .arg("--fetch-episodes")
-        .arg(&series_url)
-        .output()
-        .map_err(|e| format!("Failed to execute Python script: {}", e))?;
-    
-    if !output.status.success() {
-        return Err(String::from_utf8_lossy(&output.stderr).to_string());
-    }
-    
-    let episodes: Vec<Episode> = serde_json::from_slice(&output.stdout)
-        .map_err(|e| format!("Failed to parse episodes: {}", e))?;
-    
-    Ok(episodes)
+async fn fetch_episodes(
+    app_handle: tauri::AppHandle,
+    extractor: tauri::State<'_, tver::Extractor>,
+    series_url: String,
+) -> Result<Vec<Episode>, String> {
+    extractor.fetch_episodes(&series_url).await.map_err(|err| {
+        let mut report = reports::FailureReport::new("", &series_url, "fetch_episodes")
+            .with_stderr(err.message.clone());
+        if let Some(status) = err.http_status {
+            report = report.with_http_status(status);
+        }
+        let _ = reports::file_report(&app_handle, &report);
+        err.into()
+    })
 }
 
-// Download episodes
+/// Read just the `debug` flag out of the on-disk config, without going
+/// through the full `load_config` command (which also creates a default
+/// config file as a side effect).
+fn config_debug(app_handle: &tauri::AppHandle) -> Option<bool> {
+    let app_dir = app_handle.path_resolver().app_data_dir()?;
+    let config_str = std::fs::read_to_string(app_dir.join("config.json")).ok()?;
+    let config: Config = serde_json::from_str(&config_str).ok()?;
+    Some(config.debug)
+}
+
+// Resolve the HLS stream manifest for an episode
 #[tauri::command]
-async fn download_episodes(
+async fn resolve_stream(
     app_handle: tauri::AppHandle,
-    window: tauri::Window,
-    config: Config,
-) -> Result<String, String> {
-    let script_path = get_python_script_path(&app_handle)?;
-    let app_dir = app_handle.path_resolver()
-        .app_data_dir()
-        .ok_or("Could not get app data directory")?;
-    
-    let config_path = app_dir.join("config.json");
-    
-    // Start Python script
-    let mut child = Command::new("python3")
-        .arg(&script_path)
-        .arg("--config")
-        .arg(&config_path)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to start download: {}", e))?;
-    
-    // Stream output to frontend
-    if let Some(stdout) = child.stdout.take() {
-        use std::io::{BufRead, BufReader};
-        let reader = BufReader::new(stdout);
-        
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                // Emit progress events to frontend
-                let _ = window.emit("download-progress", line.clone());
-            }
+    extractor: tauri::State<'_, tver::Extractor>,
+    episode_id: String,
+) -> Result<tver::StreamInfo, String> {
+    extractor.resolve_stream(&episode_id).await.map_err(|err| {
+        let mut report = reports::FailureReport::new("", &episode_id, "resolve_stream")
+            .with_stderr(err.message.clone());
+        if let Some(status) = err.http_status {
+            report = report.with_http_status(status);
         }
-    }
-    
-    let status = child.wait().map_err(|e| e.to_string())?;
-    
-    if status.success() {
-        Ok("Download completed successfully".to_string())
-    } else {
-        Err("Download failed".to_string())
-    }
+        let _ = reports::file_report(&app_handle, &report);
+        err.into()
+    })
+}
+
+// Find the file yt-dlp wrote for an episode, matched by the id embedded via
+// the `[%(id)s]` marker in the configured output template. Matching on the
+// id (rather than the title) survives yt-dlp's filename sanitization, which
+// can strip or replace characters like `:`/`/`/`?` out of real episode titles.
+// yt-dlp's in-progress `.part`/`.ytdl` files are skipped, so an episode whose
+// download never finished is not reported as downloaded.
+fn find_downloaded_file(series_dir: &std::path::Path, episode_id: &str) -> Option<std::path::PathBuf> {
+    let marker = format!("[{episode_id}]");
+    let entries = std::fs::read_dir(series_dir).ok()?;
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            !matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some(ext) if ext.starts_with("part") || ext == "ytdl"
+            )
+        })
+        .find(|path| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .map(|stem| stem.contains(&marker))
+                .unwrap_or(false)
+        })
+}
+
+// Pull the `--sub-lang` value out of the configured yt-dlp options, if any
+fn subtitle_lang_from_options(options: &[String]) -> Option<String> {
+    options
+        .iter()
+        .position(|opt| opt == "--sub-lang")
+        .and_then(|i| options.get(i + 1))
+        .cloned()
 }
 
 fn main() {
-    tauri::Builder::default()
+    let builder = protocol::register(tauri::Builder::default());
+
+    builder
+        .manage(tver::Extractor::new())
+        .manage(Arc::new(download_manager::DownloadManager::new(2)))
+        .manage(Arc::new(region_guard::RegionGuard::new()))
+        .setup(|app| {
+            let app_handle = app.handle();
+            let guard = app_handle.state::<Arc<region_guard::RegionGuard>>();
+            guard
+                .inner()
+                .clone()
+                .spawn_watcher(app_handle, std::time::Duration::from_secs(60));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
-            check_vpn,
+            region_guard::check_vpn,
             load_config,
             save_config,
             fetch_episodes,
-            download_episodes,
+            resolve_stream,
+            archive::list_downloaded,
+            archive::is_downloaded,
+            archive::remove_from_archive,
+            download_manager::start_download,
+            download_manager::cancel_download,
+            download_manager::cancel_all,
+            download_manager::download_status,
+            reports::get_reports,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,111 @@
+// Parses yt-dlp's line-oriented stdout into structured progress events.
+
+use crate::DownloadProgress;
+
+/// Tracks the episode currently being reported on for one download job, so
+/// lines that don't repeat the filename (percentage updates, merge
+/// messages) can still be attributed to the right episode.
+pub struct ProgressParser {
+    series_name: String,
+    current_title: String,
+}
+
+impl ProgressParser {
+    pub fn new(series_name: String) -> Self {
+        Self {
+            series_name,
+            current_title: String::new(),
+        }
+    }
+
+    /// Parse one line of yt-dlp stdout, returning a progress event if the
+    /// line matched a recognized pattern.
+    pub fn parse_line(&mut self, line: &str) -> Option<DownloadProgress> {
+        if let Some(path) = line.strip_prefix("[download] Destination: ") {
+            self.current_title = title_from_path(path);
+            return Some(self.event("downloading", 0.0));
+        }
+
+        // yt-dlp's final per-file summary line, e.g.
+        // `[download] 100% of 123.45MiB in 00:00:12`, looks like a percent
+        // update but has no trailing `at <speed> ETA <eta>` - treat it as
+        // the terminal status for a plain (non-merged) single-stream
+        // download instead of another "downloading" event.
+        if line.starts_with("[download]") && line.contains(" in ") && !line.contains(" at ") {
+            return Some(self.event("completed", 100.0));
+        }
+
+        if let Some(percent) = parse_percent(line) {
+            return Some(self.event("downloading", percent));
+        }
+
+        // Extraction-phase lines, e.g. `[TVer] 12345: Downloading webpage`
+        // or `[info] 12345: Downloading 1 format(s)`, come before the first
+        // `[download] Destination:` line, so give the UI something to show
+        // between job start and the first percentage update.
+        if let Some(label) = extraction_label(line) {
+            if self.current_title.is_empty() {
+                self.current_title = label;
+            }
+            return Some(self.event("extracting", 0.0));
+        }
+
+        if line.contains("has already been downloaded")
+            || line.starts_with("[Merger]")
+            || line.contains("Merging formats into")
+        {
+            return Some(self.event("completed", 100.0));
+        }
+
+        if let Some(rest) = line.strip_prefix("ERROR:") {
+            self.current_title = if self.current_title.is_empty() {
+                rest.trim().to_string()
+            } else {
+                self.current_title.clone()
+            };
+            return Some(self.event("error", 0.0));
+        }
+
+        None
+    }
+
+    fn event(&self, status: &str, progress: f32) -> DownloadProgress {
+        DownloadProgress {
+            series_name: self.series_name.clone(),
+            episode_title: self.current_title.clone(),
+            status: status.to_string(),
+            progress,
+        }
+    }
+}
+
+/// Parse the percentage out of a `[download]  42.5% of ...` style line.
+fn parse_percent(line: &str) -> Option<f32> {
+    let rest = line.strip_prefix("[download]")?.trim_start();
+    let percent = rest.split_whitespace().next()?.strip_suffix('%')?;
+    percent.parse().ok()
+}
+
+/// Pull an id/label out of a `[Extractor] id: message` style line. Returns
+/// `None` for lines we already handle elsewhere (`[download]`, `[Merger]`).
+fn extraction_label(line: &str) -> Option<String> {
+    if line.starts_with("[download]") || line.starts_with("[Merger]") {
+        return None;
+    }
+    let rest = line.strip_prefix('[')?;
+    let (_tag, after_bracket) = rest.split_once("] ")?;
+    let label = after_bracket.split(':').next()?.trim();
+    if label.is_empty() {
+        None
+    } else {
+        Some(label.to_string())
+    }
+}
+
+fn title_from_path(path: &str) -> String {
+    std::path::Path::new(path.trim())
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_else(|| path.trim())
+        .to_string()
+}
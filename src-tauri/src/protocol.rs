@@ -0,0 +1,159 @@
+// Custom `tverdl://` protocol for serving downloaded media to the webview,
+// with HTTP Range support so <video> can seek.
+
+use std::io::SeekFrom;
+
+use tauri::http::{Request, Response, ResponseBuilder};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::archive;
+
+const SCHEME: &str = "tverdl";
+
+// Tauri's v1 protocol API returns a fully-materialized Vec<u8> body (no
+// streaming), so an unranged request past this size is served as a forced
+// 206 for the first chunk instead of buffering the whole file.
+const MAX_CHUNK_BYTES: u64 = 8 * 1024 * 1024;
+
+pub fn register(builder: tauri::Builder<tauri::Wry>) -> tauri::Builder<tauri::Wry> {
+    builder.register_asynchronous_uri_scheme_protocol(SCHEME, move |app, request, responder| {
+        let app = app.clone();
+        let request = clone_request(request);
+        tauri::async_runtime::spawn(async move {
+            let response = handle_request(&app, &request)
+                .await
+                .unwrap_or_else(error_response);
+            responder.respond(response);
+        });
+    })
+}
+
+fn clone_request(request: &Request) -> OwnedRequest {
+    OwnedRequest {
+        uri: request.uri().to_string(),
+        range: request
+            .headers()
+            .get("range")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string()),
+    }
+}
+
+struct OwnedRequest {
+    uri: String,
+    range: Option<String>,
+}
+
+async fn handle_request(
+    app: &tauri::AppHandle,
+    request: &OwnedRequest,
+) -> Result<Response, Box<dyn std::error::Error>> {
+    let episode_id = episode_id_from_uri(&request.uri).ok_or("missing episode id")?;
+
+    let cache = archive::open_cache(app)?;
+    let entry = match cache.get(&episode_id)? {
+        Some(entry) => entry,
+        None => return Ok(not_found()),
+    };
+
+    let mut file = match tokio::fs::File::open(&entry.file_path).await {
+        Ok(file) => file,
+        Err(_) => return Ok(not_found()),
+    };
+
+    let file_len = file.metadata().await?.len();
+
+    let (start, end, forced_partial) = match request.range.as_deref().map(parse_range) {
+        Some(Some((start, end))) => (start, end.min(file_len.saturating_sub(1)), false),
+        Some(None) => return Ok(range_not_satisfiable(file_len)),
+        None => {
+            let end = file_len.saturating_sub(1);
+            if file_len > MAX_CHUNK_BYTES {
+                (0, MAX_CHUNK_BYTES - 1, true)
+            } else {
+                (0, end, false)
+            }
+        }
+    };
+
+    if start > end || start >= file_len {
+        return Ok(range_not_satisfiable(file_len));
+    }
+
+    let len = end - start + 1;
+    file.seek(SeekFrom::Start(start)).await?;
+    let mut body = vec![0u8; len as usize];
+    file.read_exact(&mut body).await?;
+
+    let partial = request.range.is_some() || forced_partial;
+    let status = if partial { 206 } else { 200 };
+
+    let mut response = ResponseBuilder::new()
+        .status(status)
+        .header("Accept-Ranges", "bytes")
+        .header("Content-Length", len.to_string())
+        .header("Content-Type", content_type_for(&entry.file_path));
+
+    if partial {
+        response = response.header(
+            "Content-Range",
+            format!("bytes {}-{}/{}", start, end, file_len),
+        );
+    }
+
+    Ok(response.body(body)?)
+}
+
+fn episode_id_from_uri(uri: &str) -> Option<String> {
+    uri.rsplit('/').next().filter(|s| !s.is_empty()).map(String::from)
+}
+
+/// Parse a `bytes=start-end` Range header (an open end means "to the end of
+/// the file"). Returns `None` when the header is malformed; suffix ranges
+/// such as `bytes=-500` are not supported and also yield `None`.
+fn parse_range(header: &str) -> Option<(u64, u64)> {
+    let spec = header.strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    let start: u64 = start.parse().ok()?;
+    let end: u64 = if end.is_empty() {
+        u64::MAX
+    } else {
+        end.parse().ok()?
+    };
+    Some((start, end))
+}
+
+fn content_type_for(path: &str) -> &'static str {
+    match std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+    {
+        Some("mp4") => "video/mp4",
+        Some("webm") => "video/webm",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        _ => "application/octet-stream",
+    }
+}
+
+fn not_found() -> Response {
+    ResponseBuilder::new()
+        .status(404)
+        .body(Vec::new())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+fn range_not_satisfiable(file_len: u64) -> Response {
+    ResponseBuilder::new()
+        .status(416)
+        .header("Content-Range", format!("bytes */{}", file_len))
+        .body(Vec::new())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
+
+fn error_response(_err: Box<dyn std::error::Error>) -> Response {
+    ResponseBuilder::new()
+        .status(404)
+        .body(Vec::new())
+        .unwrap_or_else(|_| Response::new(Vec::new()))
+}
@@ -0,0 +1,139 @@
+// Configurable region gate: checks the exit country against `Config`,
+// caching results for a short TTL and optionally watching in the background.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Manager;
+use tokio::sync::RwLock;
+
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// The detected exit region, as surfaced to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct RegionStatus {
+    pub country: String,
+    pub ip: String,
+    pub allowed: bool,
+}
+
+struct Cached {
+    status: RegionStatus,
+    checked_at: Instant,
+}
+
+pub struct RegionGuard {
+    client: reqwest::Client,
+    cached: RwLock<Option<Cached>>,
+}
+
+impl RegionGuard {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            cached: RwLock::new(None),
+        }
+    }
+
+    /// Return the cached status if it's still within the TTL, otherwise
+    /// perform a fresh check against the configured geo-IP endpoints.
+    pub async fn check(
+        &self,
+        required_country: &str,
+        endpoints: &[String],
+    ) -> Result<RegionStatus, String> {
+        if let Some(cached) = self.cached.read().await.as_ref() {
+            if cached.checked_at.elapsed() < CACHE_TTL {
+                return Ok(cached.status.clone());
+            }
+        }
+
+        let status = self.detect(required_country, endpoints).await?;
+        self.store(status.clone()).await;
+        Ok(status)
+    }
+
+    async fn store(&self, status: RegionStatus) {
+        *self.cached.write().await = Some(Cached {
+            status,
+            checked_at: Instant::now(),
+        });
+    }
+
+    async fn detect(
+        &self,
+        required_country: &str,
+        endpoints: &[String],
+    ) -> Result<RegionStatus, String> {
+        for endpoint in endpoints {
+            let Ok(response) = self.client.get(endpoint).send().await else {
+                continue;
+            };
+            let Ok(data) = response.json::<serde_json::Value>().await else {
+                continue;
+            };
+
+            let country = data
+                .get("country_code")
+                .or_else(|| data.get("cc"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let ip = data
+                .get("ip")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+
+            return Ok(RegionStatus {
+                allowed: country == required_country,
+                country,
+                ip,
+            });
+        }
+
+        Err("Could not verify region".to_string())
+    }
+
+    /// Spawn a background task that re-checks on `interval` and emits
+    /// `vpn-status` whenever the detected country changes. Config is
+    /// re-read every tick so edits made via `save_config` take effect on
+    /// the next check instead of requiring a restart.
+    pub fn spawn_watcher(self: Arc<Self>, app_handle: tauri::AppHandle, interval: Duration) {
+        tauri::async_runtime::spawn(async move {
+            let mut last_country: Option<String> = None;
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let Ok(config) = crate::load_config(app_handle.clone()).await else {
+                    continue;
+                };
+                let Ok(status) = self
+                    .detect(&config.required_country, &config.geo_ip_endpoints)
+                    .await
+                else {
+                    continue;
+                };
+                self.store(status.clone()).await;
+
+                if last_country.as_deref() != Some(status.country.as_str()) {
+                    last_country = Some(status.country.clone());
+                    let _ = app_handle.emit_all("vpn-status", status);
+                }
+            }
+        });
+    }
+}
+
+// Check the current exit region against the configured requirement
+#[tauri::command]
+pub async fn check_vpn(
+    app_handle: tauri::AppHandle,
+    guard: tauri::State<'_, Arc<RegionGuard>>,
+) -> Result<RegionStatus, String> {
+    let config = crate::load_config(app_handle.clone()).await?;
+    guard
+        .check(&config.required_country, &config.geo_ip_endpoints)
+        .await
+}
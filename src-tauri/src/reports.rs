@@ -0,0 +1,112 @@
+// Structured failure reports for extraction and download errors.
+// JSON by default; YAML behind the optional `report-yaml` feature.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::Manager;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureReport {
+    pub series_name: String,
+    pub url: String,
+    pub operation: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub http_status: Option<u16>,
+    pub timestamp: u64,
+}
+
+impl FailureReport {
+    pub fn new(
+        series_name: impl Into<String>,
+        url: impl Into<String>,
+        operation: impl Into<String>,
+    ) -> Self {
+        Self {
+            series_name: series_name.into(),
+            url: url.into(),
+            operation: operation.into(),
+            stdout: String::new(),
+            stderr: String::new(),
+            http_status: None,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        }
+    }
+
+    pub fn with_stdout(mut self, stdout: impl Into<String>) -> Self {
+        self.stdout = stdout.into();
+        self
+    }
+
+    pub fn with_stderr(mut self, stderr: impl Into<String>) -> Self {
+        self.stderr = stderr.into();
+        self
+    }
+
+    pub fn with_http_status(mut self, status: u16) -> Self {
+        self.http_status = Some(status);
+        self
+    }
+}
+
+fn reports_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_dir = app_handle
+        .path_resolver()
+        .app_data_dir()
+        .ok_or("Could not get app data directory")?;
+    let dir = app_dir.join("reports");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// Persist a failure report to disk and, if `Config.debug` is set, emit
+/// `report-available` with its path so the UI can surface it immediately.
+pub fn file_report(app_handle: &tauri::AppHandle, report: &FailureReport) -> Result<PathBuf, String> {
+    let dir = reports_dir(app_handle)?;
+    let filename = format!("{}-{}.{}", report.operation, report.timestamp, extension());
+    let path = dir.join(filename);
+
+    std::fs::write(&path, serialize(report)?).map_err(|e| e.to_string())?;
+
+    if crate::config_debug(app_handle).unwrap_or(false) {
+        let _ = app_handle.emit_all("report-available", path.to_string_lossy().to_string());
+    }
+
+    Ok(path)
+}
+
+#[cfg(not(feature = "report-yaml"))]
+fn serialize(report: &FailureReport) -> Result<String, String> {
+    serde_json::to_string_pretty(report).map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "report-yaml")]
+fn serialize(report: &FailureReport) -> Result<String, String> {
+    serde_yaml::to_string(report).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "report-yaml"))]
+fn extension() -> &'static str {
+    "json"
+}
+
+#[cfg(feature = "report-yaml")]
+fn extension() -> &'static str {
+    "yaml"
+}
+
+// List the paths of every failure report written so far
+#[tauri::command]
+pub async fn get_reports(app_handle: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let dir = reports_dir(&app_handle)?;
+    let mut paths: Vec<String> = std::fs::read_dir(&dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
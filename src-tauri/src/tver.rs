@@ -0,0 +1,164 @@
+// Native TVer extraction client.
+
+use crate::Episode;
+use serde::Deserialize;
+
+const PLATFORM_API_BASE: &str = "https://platform-api.tver.jp";
+
+/// Resolved playback info for a single episode.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StreamInfo {
+    pub episode_id: String,
+    pub hls_url: String,
+    pub subtitle_url: Option<String>,
+}
+
+/// An extraction failure, carrying the HTTP status when the failure came
+/// from a non-success TVer API response rather than a network/parse error.
+#[derive(Debug, Clone)]
+pub struct ExtractorError {
+    pub message: String,
+    pub http_status: Option<u16>,
+}
+
+impl ExtractorError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            http_status: None,
+        }
+    }
+
+    fn with_status(status: reqwest::StatusCode) -> Self {
+        Self {
+            message: format!("TVer API returned {status}"),
+            http_status: Some(status.as_u16()),
+        }
+    }
+}
+
+impl std::fmt::Display for ExtractorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<ExtractorError> for String {
+    fn from(err: ExtractorError) -> Self {
+        err.message
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformSeriesEpisodes {
+    episodes: Vec<PlatformEpisode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformEpisode {
+    id: String,
+    title: String,
+    #[serde(rename = "episodeURL")]
+    episode_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlatformEpisodeDetail {
+    #[serde(rename = "hlsURL")]
+    hls_url: String,
+    #[serde(rename = "subtitleURL")]
+    subtitle_url: Option<String>,
+}
+
+/// Talks to TVer's platform API to resolve series URLs into episodes and
+/// episodes into stream manifests. Owns a single `reqwest::Client` that is
+/// reused across calls rather than constructed per-request. Cheap to clone:
+/// `reqwest::Client` is itself a handle around a shared connection pool.
+#[derive(Clone)]
+pub struct Extractor {
+    client: reqwest::Client,
+}
+
+impl Extractor {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Resolve a series URL (e.g. `https://tver.jp/series/srxxxxxxxx`) into
+    /// its episode list.
+    pub async fn fetch_episodes(&self, series_url: &str) -> Result<Vec<Episode>, ExtractorError> {
+        let series_id = Self::series_id_from_url(series_url)?;
+        let endpoint = format!(
+            "{PLATFORM_API_BASE}/service/platform_event/v1/callSeriesEpisodes/{series_id}"
+        );
+
+        let response = self
+            .client
+            .get(&endpoint)
+            .send()
+            .await
+            .map_err(|e| ExtractorError::new(format!("Failed to reach TVer: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(ExtractorError::with_status(response.status()));
+        }
+
+        let body: PlatformSeriesEpisodes = response
+            .json()
+            .await
+            .map_err(|e| ExtractorError::new(format!("Failed to parse TVer response: {e}")))?;
+
+        Ok(body
+            .episodes
+            .into_iter()
+            .map(|ep| Episode {
+                id: ep.id,
+                title: ep.title,
+                url: ep.episode_url,
+            })
+            .collect())
+    }
+
+    /// Resolve an episode id into its HLS stream manifest.
+    pub async fn resolve_stream(&self, episode_id: &str) -> Result<StreamInfo, ExtractorError> {
+        let endpoint =
+            format!("{PLATFORM_API_BASE}/service/platform_event/v1/callEpisode/{episode_id}");
+
+        let response = self
+            .client
+            .get(&endpoint)
+            .send()
+            .await
+            .map_err(|e| ExtractorError::new(format!("Failed to reach TVer: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(ExtractorError::with_status(response.status()));
+        }
+
+        let body: PlatformEpisodeDetail = response
+            .json()
+            .await
+            .map_err(|e| ExtractorError::new(format!("Failed to parse TVer response: {e}")))?;
+
+        Ok(StreamInfo {
+            episode_id: episode_id.to_string(),
+            hls_url: body.hls_url,
+            subtitle_url: body.subtitle_url,
+        })
+    }
+
+    fn series_id_from_url(series_url: &str) -> Result<String, ExtractorError> {
+        series_url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(series_url)
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_string())
+            .ok_or_else(|| ExtractorError::new(format!("Could not extract series id from {series_url}")))
+    }
+}